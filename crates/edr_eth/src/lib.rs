@@ -0,0 +1,4 @@
+pub mod remote;
+
+pub use revm::primitives::{Address, B256, U256};
+pub use std::collections::HashMap;
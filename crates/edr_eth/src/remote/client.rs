@@ -0,0 +1,556 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use rand::Rng;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use revm::primitives::{AccountInfo, Address, Bytecode, Bytes, U256};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+
+use super::{
+    eth::{BlockResponse, ProofResponse},
+    error::RpcClientError,
+    BlockSpec,
+};
+
+/// Configuration for retrying JSON-RPC requests that fail transiently (a
+/// dropped connection, a `429 Too Many Requests`, a `5xx`) before giving up.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryConfig {
+    /// The maximum number of times to retry a request before giving up, not
+    /// counting the initial attempt (e.g. `max_retries = 5` allows up to 6
+    /// total attempts).
+    pub max_retries: u32,
+    /// The maximum total time to spend retrying, across all attempts.
+    pub max_elapsed: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            max_elapsed: Duration::from_secs(30),
+        }
+    }
+}
+
+/// A client for submitting JSON-RPC requests to a remote Ethereum node.
+#[derive(Debug)]
+pub struct RpcClient {
+    url: String,
+    #[allow(dead_code)]
+    cache_dir: PathBuf,
+    http_client: reqwest::Client,
+    next_id: AtomicU64,
+    retry_config: RetryConfig,
+}
+
+impl RpcClient {
+    /// Constructs a new instance for the given JSON-RPC `url`, caching
+    /// responses under `cache_dir`, using the default retry budget and no
+    /// extra HTTP headers.
+    pub fn new(url: &str, cache_dir: PathBuf) -> Self {
+        Self::with_retry_config(url, cache_dir, RetryConfig::default())
+    }
+
+    /// Constructs a new instance, like [`RpcClient::new`], but with an
+    /// explicit retry budget instead of the default one.
+    pub fn with_retry_config(url: &str, cache_dir: PathBuf, retry_config: RetryConfig) -> Self {
+        Self::with_headers(url, cache_dir, retry_config, &HashMap::new())
+    }
+
+    /// Constructs a new instance, like [`RpcClient::with_retry_config`], that
+    /// additionally attaches `http_headers` to every request it sends - e.g.
+    /// an `Authorization` bearer token or a provider-specific API-key header
+    /// required by a gated RPC endpoint. Header names/values that aren't
+    /// valid HTTP header syntax are skipped.
+    pub fn with_headers(
+        url: &str,
+        cache_dir: PathBuf,
+        retry_config: RetryConfig,
+        http_headers: &HashMap<String, String>,
+    ) -> Self {
+        let mut headers = HeaderMap::new();
+        for (name, value) in http_headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(value),
+            ) {
+                headers.insert(name, value);
+            }
+        }
+
+        let http_client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .expect("building the HTTP client from well-formed headers should never fail");
+
+        Self {
+            url: url.to_string(),
+            cache_dir,
+            http_client,
+            next_id: AtomicU64::new(0),
+            retry_config,
+        }
+    }
+
+    /// Submits `request_body` as a raw HTTP POST, retrying transient
+    /// failures (connection errors, `429 Too Many Requests`, `5xx`) with
+    /// exponential backoff and jitter, honoring the server's `Retry-After`
+    /// header when present, until the retry budget in `self.retry_config` is
+    /// exhausted.
+    async fn send_with_retry(&self, request_body: &Value) -> Result<String, RpcClientError> {
+        let start = Instant::now();
+        let mut attempt = 0u32;
+
+        loop {
+            attempt += 1;
+
+            match self.http_client.post(&self.url).json(request_body).send().await {
+                Ok(response)
+                    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+                        || response.status().is_server_error() =>
+                {
+                    // `Retry-After` is only meaningful on 429; a 5xx has no
+                    // such hint, so fall back to our own backoff schedule.
+                    let retry_after = (response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS)
+                        .then(|| {
+                            response
+                                .headers()
+                                .get(reqwest::header::RETRY_AFTER)
+                                .and_then(|value| value.to_str().ok())
+                                .and_then(|value| value.parse::<u64>().ok())
+                                .map(Duration::from_secs)
+                        })
+                        .flatten();
+
+                    if !self.should_retry(attempt, start) {
+                        return Err(RpcClientError::RetriesExhausted { attempts: attempt });
+                    }
+
+                    tokio::time::sleep(retry_after.unwrap_or_else(|| self.backoff_delay(attempt)))
+                        .await;
+                }
+                Ok(response) => return Ok(response.text().await?),
+                Err(error) => {
+                    if !self.should_retry(attempt, start) {
+                        return Err(RpcClientError::Transport(error));
+                    }
+
+                    tokio::time::sleep(self.backoff_delay(attempt)).await;
+                }
+            }
+        }
+    }
+
+    fn should_retry(&self, attempts_made: u32, start: Instant) -> bool {
+        // `attempts_made` counts the attempt that just failed, which isn't
+        // itself a retry, so a budget of `max_retries` retries permits up to
+        // `max_retries` more attempts after it.
+        attempts_made <= self.retry_config.max_retries
+            && start.elapsed() < self.retry_config.max_elapsed
+    }
+
+    /// Exponential backoff with jitter: `200ms * 2^(attempt - 1)`, plus up to
+    /// 100ms of random jitter so that concurrent clients hitting the same
+    /// rate limit don't all retry in lockstep.
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let base = Duration::from_millis(
+            200u64.saturating_mul(2u64.saturating_pow(attempt.saturating_sub(1))),
+        );
+        let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..100));
+
+        base + jitter
+    }
+
+    fn block_spec_param(block_spec: BlockSpec) -> Value {
+        match block_spec {
+            BlockSpec::Number(number) => json!(format!("0x{number:x}")),
+            BlockSpec::Tag(tag) => json!(tag.as_str()),
+        }
+    }
+
+    /// Submits a single JSON-RPC request for `method` with `params`.
+    pub(super) async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: impl Serialize,
+    ) -> Result<T, RpcClientError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+
+        let body = self.send_with_retry(&request).await?;
+
+        Self::parse_single(body)
+    }
+
+    fn parse_single<T: DeserializeOwned>(body: String) -> Result<T, RpcClientError> {
+        let response: JsonRpcResponse<T> = serde_json::from_str(&body)?;
+        response.into_result()
+    }
+
+    /// Whether a block with the given number is old enough that its contents
+    /// are immutable, and therefore safe to cache indefinitely.
+    pub async fn is_cacheable_block_number(&self, block_number: &U256) -> Result<bool, RpcClientError> {
+        let latest = self
+            .get_block_by_number(BlockSpec::Tag(super::BlockTag::Latest))
+            .await?;
+
+        // Leave a safety margin for reorgs rather than trusting "latest"
+        // outright.
+        const SAFETY_MARGIN: u64 = 32;
+        Ok(*block_number + U256::from(SAFETY_MARGIN) <= latest.number)
+    }
+
+    /// Retrieves the account info of `address` at `block`.
+    pub async fn get_account_info(
+        &self,
+        address: &Address,
+        block: Option<BlockSpec>,
+    ) -> Result<AccountInfo, RpcClientError> {
+        let block = block.unwrap_or(BlockSpec::Tag(super::BlockTag::Latest));
+        let response: AccountInfoValue = self
+            .call(
+                "eth_getAccountInfo",
+                (address, Self::block_spec_param(block)),
+            )
+            .await?;
+
+        Ok(response.into_account_info())
+    }
+
+    /// Retrieves the value stored at `index` of `address`'s storage, at
+    /// `block`.
+    pub async fn get_storage_at(
+        &self,
+        address: &Address,
+        index: U256,
+        block: Option<BlockSpec>,
+    ) -> Result<U256, RpcClientError> {
+        let block = block.unwrap_or(BlockSpec::Tag(super::BlockTag::Latest));
+        self.call(
+            "eth_getStorageAt",
+            (
+                address,
+                format!("0x{index:x}"),
+                Self::block_spec_param(block),
+            ),
+        )
+        .await
+    }
+
+    /// Retrieves the block matching `block_spec`.
+    pub async fn get_block_by_number(
+        &self,
+        block_spec: BlockSpec,
+    ) -> Result<BlockResponse, RpcClientError> {
+        self.call(
+            "eth_getBlockByNumber",
+            (Self::block_spec_param(block_spec), false),
+        )
+        .await
+    }
+
+    /// Retrieves a Merkle-Patricia proof of `address`'s account and, if
+    /// `slots` is non-empty, of the given storage slots, at `block`.
+    pub async fn get_proof(
+        &self,
+        address: &Address,
+        slots: &[U256],
+        block: Option<BlockSpec>,
+    ) -> Result<ProofResponse, RpcClientError> {
+        let block = block.unwrap_or(BlockSpec::Tag(super::BlockTag::Latest));
+        let slots: Vec<String> = slots.iter().map(|slot| format!("0x{slot:x}")).collect();
+
+        self.call(
+            "eth_getProof",
+            (address, slots, Self::block_spec_param(block)),
+        )
+        .await
+    }
+
+    /// Retrieves the deployed bytecode of `address` at `block`.
+    pub async fn get_code(
+        &self,
+        address: &Address,
+        block: Option<BlockSpec>,
+    ) -> Result<Bytes, RpcClientError> {
+        let block = block.unwrap_or(BlockSpec::Tag(super::BlockTag::Latest));
+        self.call("eth_getCode", (address, Self::block_spec_param(block)))
+            .await
+    }
+
+    /// Retrieves `block_count` blocks worth of fee history, up to and
+    /// including `newest_block`, along with the requested priority-fee
+    /// `reward_percentiles`.
+    pub async fn fee_history(
+        &self,
+        block_count: u64,
+        newest_block: BlockSpec,
+        reward_percentiles: &[f64],
+    ) -> Result<super::eth::FeeHistoryResult, RpcClientError> {
+        self.call(
+            "eth_feeHistory",
+            (
+                format!("0x{block_count:x}"),
+                Self::block_spec_param(newest_block),
+                reward_percentiles,
+            ),
+        )
+        .await
+    }
+
+    /// Resolves a batch of account-info and storage-slot requests in a
+    /// single JSON-RPC round-trip, by serializing them as one JSON-RPC batch
+    /// array and demultiplexing the (possibly reordered) responses by id.
+    pub async fn get_account_and_storage_batch(
+        &self,
+        requests: &[(Address, Vec<U256>)],
+        block: Option<BlockSpec>,
+    ) -> Result<Vec<(Address, AccountInfo, Vec<(U256, U256)>)>, RpcClientError> {
+        let block = block.unwrap_or(BlockSpec::Tag(super::BlockTag::Latest));
+        let block_param = Self::block_spec_param(block);
+
+        let mut batch = Vec::new();
+        let mut account_ids = Vec::with_capacity(requests.len());
+        let mut slot_ids = Vec::new();
+
+        for (address, slots) in requests {
+            let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+            batch.push(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": "eth_getAccountInfo",
+                "params": (address, block_param.clone()),
+            }));
+            account_ids.push(id);
+
+            let mut ids_for_address = Vec::with_capacity(slots.len());
+            for slot in slots {
+                let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+                batch.push(json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "method": "eth_getStorageAt",
+                    "params": (address, format!("0x{slot:x}"), block_param.clone()),
+                }));
+                ids_for_address.push(id);
+            }
+            slot_ids.push(ids_for_address);
+        }
+
+        let body = self.send_with_retry(&Value::Array(batch)).await?;
+
+        let items: Vec<BatchResponseItem> = serde_json::from_str(&body)?;
+        let mut by_id = demultiplex_by_id(items)?;
+
+        let mut results = Vec::with_capacity(requests.len());
+        for (index, (address, slots)) in requests.iter().enumerate() {
+            let account_value = by_id
+                .remove(&account_ids[index])
+                .ok_or_else(|| RpcClientError::JsonRpc("missing account response".to_string()))?;
+            let account_response: AccountInfoValue = serde_json::from_value(account_value)?;
+            let account = account_response.into_account_info();
+
+            let mut slot_values = Vec::with_capacity(slots.len());
+            for (slot_index, slot) in slots.iter().enumerate() {
+                let value = by_id
+                    .remove(&slot_ids[index][slot_index])
+                    .ok_or_else(|| RpcClientError::JsonRpc("missing storage response".to_string()))?;
+                let value: U256 = serde_json::from_value(value)?;
+                slot_values.push((*slot, value));
+            }
+
+            results.push((*address, account, slot_values));
+        }
+
+        Ok(results)
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountInfoValue {
+    nonce: u64,
+    balance: U256,
+    code: Option<Bytes>,
+}
+
+impl AccountInfoValue {
+    fn into_account_info(self) -> AccountInfo {
+        let code = self.code.map(Bytecode::new_raw);
+        AccountInfo {
+            nonce: self.nonce,
+            balance: self.balance,
+            code_hash: code
+                .as_ref()
+                .map_or(revm::primitives::KECCAK_EMPTY, |code| code.hash_slow()),
+            code,
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct BatchResponseItem {
+    id: u64,
+    result: Option<Value>,
+    error: Option<JsonRpcError>,
+}
+
+/// Demultiplexes a JSON-RPC batch response, which may arrive in a different
+/// order than the requests were submitted in, keyed by request id.
+fn demultiplex_by_id(
+    items: Vec<BatchResponseItem>,
+) -> Result<std::collections::HashMap<u64, Value>, RpcClientError> {
+    items
+        .into_iter()
+        .map(|item| {
+            if let Some(error) = item.error {
+                return Err(RpcClientError::JsonRpc(format!(
+                    "{} ({})",
+                    error.message, error.code
+                )));
+            }
+
+            let result = item
+                .result
+                .ok_or_else(|| RpcClientError::JsonRpc("empty response".to_string()))?;
+
+            Ok((item.id, result))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn demultiplex_by_id_reorders_responses() {
+        // The remote node is free to respond out of order; callers rely on
+        // `demultiplex_by_id` to match each result back to its request id.
+        let items = vec![
+            BatchResponseItem {
+                id: 2,
+                result: Some(json!("second")),
+                error: None,
+            },
+            BatchResponseItem {
+                id: 0,
+                result: Some(json!("first")),
+                error: None,
+            },
+            BatchResponseItem {
+                id: 1,
+                result: Some(json!("third")),
+                error: None,
+            },
+        ];
+
+        let by_id = demultiplex_by_id(items).expect("no errors in batch");
+
+        assert_eq!(by_id.get(&0), Some(&json!("first")));
+        assert_eq!(by_id.get(&1), Some(&json!("third")));
+        assert_eq!(by_id.get(&2), Some(&json!("second")));
+    }
+
+    #[test]
+    fn demultiplex_by_id_surfaces_individual_errors() {
+        let items = vec![
+            BatchResponseItem {
+                id: 0,
+                result: Some(json!("ok")),
+                error: None,
+            },
+            BatchResponseItem {
+                id: 1,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32000,
+                    message: "execution reverted".to_string(),
+                }),
+            },
+        ];
+
+        let error = demultiplex_by_id(items).expect_err("one item errored");
+        assert!(matches!(error, RpcClientError::JsonRpc(_)));
+    }
+
+    #[test]
+    fn backoff_delay_grows_exponentially() {
+        let client = RpcClient::new("http://localhost", PathBuf::new());
+
+        // Jitter adds up to 100ms on top of the exponential base, so compare
+        // the lower bound of each successive attempt's delay.
+        assert!(client.backoff_delay(1) >= Duration::from_millis(200));
+        assert!(client.backoff_delay(2) >= Duration::from_millis(400));
+        assert!(client.backoff_delay(3) >= Duration::from_millis(800));
+    }
+
+    #[test]
+    fn should_retry_respects_max_retries() {
+        let client = RpcClient::with_retry_config(
+            "http://localhost",
+            PathBuf::new(),
+            RetryConfig {
+                max_retries: 3,
+                max_elapsed: Duration::from_secs(60),
+            },
+        );
+
+        let start = Instant::now();
+        assert!(client.should_retry(1, start));
+        assert!(client.should_retry(2, start));
+        assert!(client.should_retry(3, start));
+        assert!(!client.should_retry(4, start));
+    }
+
+    #[test]
+    fn should_retry_respects_max_elapsed() {
+        let client = RpcClient::with_retry_config(
+            "http://localhost",
+            PathBuf::new(),
+            RetryConfig {
+                max_retries: u32::MAX,
+                max_elapsed: Duration::from_millis(0),
+            },
+        );
+
+        assert!(!client.should_retry(1, Instant::now()));
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct JsonRpcResponse<T> {
+    result: Option<T>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(serde::Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+impl<T> JsonRpcResponse<T> {
+    fn into_result(self) -> Result<T, RpcClientError> {
+        if let Some(error) = self.error {
+            return Err(RpcClientError::JsonRpc(format!(
+                "{} ({})",
+                error.message, error.code
+            )));
+        }
+
+        self.result
+            .ok_or_else(|| RpcClientError::JsonRpc("empty response".to_string()))
+    }
+}
@@ -0,0 +1,22 @@
+/// An error that can occur while making a JSON-RPC request to a remote
+/// Ethereum node.
+#[derive(Debug, thiserror::Error)]
+pub enum RpcClientError {
+    /// The underlying HTTP request failed, even after exhausting the
+    /// configured retry budget
+    #[error("Failed to send request: {0}")]
+    Transport(#[from] reqwest::Error),
+    /// The remote node returned a JSON-RPC error response
+    #[error("RPC error: {0}")]
+    JsonRpc(String),
+    /// The response could not be deserialized into the expected shape
+    #[error("Failed to deserialize response: {0}")]
+    Deserialize(#[from] serde_json::Error),
+    /// The retry budget (max attempts or max elapsed time) was exhausted
+    /// while retrying a request
+    #[error("Exhausted retry budget after {attempts} attempt(s)")]
+    RetriesExhausted {
+        /// The number of attempts made before giving up
+        attempts: u32,
+    },
+}
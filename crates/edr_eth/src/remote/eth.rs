@@ -0,0 +1,69 @@
+//! JSON-RPC response types for the `eth_*` namespace.
+
+use revm::primitives::{Address, Bytes, B256, U256};
+use serde::Deserialize;
+
+/// The response to an `eth_getBlockByNumber` request (the fields this crate
+/// currently cares about).
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockResponse {
+    /// The block number
+    pub number: U256,
+    /// The state root of the block
+    pub state_root: B256,
+    /// The base fee per gas of the block, if the block is post-EIP-1559
+    pub base_fee_per_gas: Option<U256>,
+}
+
+/// The response to an `eth_getProof` request.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofResponse {
+    /// The address the proof was requested for
+    pub address: Address,
+    /// The account's nonce
+    pub nonce: u64,
+    /// The account's balance
+    pub balance: U256,
+    /// The hash of the account's code
+    pub code_hash: B256,
+    /// The root of the account's storage trie
+    pub storage_hash: B256,
+    /// The Merkle-Patricia proof of the account, rooted at the block's state
+    /// root
+    pub account_proof: Vec<Bytes>,
+    /// The Merkle-Patricia proof of each requested storage slot, rooted at
+    /// `storage_hash`
+    pub storage_proof: Vec<StorageProof>,
+}
+
+/// A single storage slot's proof, as returned within an `eth_getProof`
+/// response.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageProof {
+    /// The storage slot that was requested
+    pub key: U256,
+    /// The value stored at `key`
+    pub value: U256,
+    /// The Merkle-Patricia proof of the slot, rooted at the account's
+    /// `storage_hash`
+    pub proof: Vec<Bytes>,
+}
+
+/// The response to an `eth_feeHistory` request.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistoryResult {
+    /// The oldest block covered by the response
+    pub oldest_block: U256,
+    /// The base fee per gas of each block in range, plus one extra entry for
+    /// the next block after the range
+    pub base_fee_per_gas: Vec<U256>,
+    /// The ratio of gas used to gas limit for each returned block
+    pub gas_used_ratio: Vec<f64>,
+    /// The requested priority-fee percentiles for each returned block, if
+    /// `reward_percentiles` was non-empty
+    pub reward: Option<Vec<Vec<U256>>>,
+}
@@ -0,0 +1,43 @@
+mod client;
+mod error;
+pub mod eth;
+
+use revm::primitives::U256;
+
+pub use client::{RetryConfig, RpcClient};
+pub use error::RpcClientError;
+
+/// A block number, or a tag referring to one relative to the remote node's
+/// view of the chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockSpec {
+    /// An absolute block number
+    Number(U256),
+    /// A named tag, resolved by the remote node
+    Tag(BlockTag),
+}
+
+/// A named block tag understood by `eth_getBlockByNumber` and friends.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockTag {
+    /// The most recent crypto-economically secure block
+    Safe,
+    /// The most recent finalized block
+    Finalized,
+    /// The most recent block in the canonical chain
+    Latest,
+    /// The next block to be produced
+    Pending,
+}
+
+impl BlockTag {
+    /// The JSON-RPC string representation of the tag.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlockTag::Safe => "safe",
+            BlockTag::Finalized => "finalized",
+            BlockTag::Latest => "latest",
+            BlockTag::Pending => "pending",
+        }
+    }
+}
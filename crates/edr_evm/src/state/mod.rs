@@ -0,0 +1,25 @@
+mod remote;
+
+use revm::primitives::B256;
+
+use edr_eth::remote::RpcClientError;
+
+pub use remote::{
+    new_fork_state, CachedRemoteState, ForkState, PendingAccess, PrefetchedState, RemoteState,
+    VerifiedRemoteState,
+};
+
+/// An error that can occur while reading state.
+#[derive(Debug, thiserror::Error)]
+pub enum StateError {
+    /// The code hash of an account doesn't correspond to any known code
+    #[error("Unable to find code with code hash: {0}")]
+    InvalidCodeHash(B256),
+    /// An `eth_getProof` response failed Merkle-Patricia proof verification
+    /// against the block's state root
+    #[error("Proof verification failed")]
+    InvalidProof,
+    /// An error that occurred while querying a remote Ethereum node
+    #[error(transparent)]
+    Remote(#[from] RpcClientError),
+}
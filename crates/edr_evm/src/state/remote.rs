@@ -1,6 +1,8 @@
 mod cached;
+mod proof;
+mod verified;
 
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use revm::{
     db::StateRef,
@@ -16,6 +18,7 @@ use edr_eth::{
 use super::StateError;
 
 pub use cached::CachedRemoteState;
+pub use verified::VerifiedRemoteState;
 
 /// A state backed by a remote Ethereum node
 #[derive(Debug)]
@@ -36,6 +39,21 @@ impl RemoteState {
         }
     }
 
+    /// Constructs a new instance from a [`BlockSpec`], resolving a block tag
+    /// such as `safe`, `finalized`, `latest`, or `pending` to a concrete
+    /// block number once, up front. Pinning to a concrete number this way
+    /// keeps the state's caching semantics stable even as the chain
+    /// advances and the tag's meaning shifts.
+    pub fn new_at_block_spec(
+        runtime: runtime::Handle,
+        client: Arc<RpcClient>,
+        block_spec: BlockSpec,
+    ) -> Result<Self, StateError> {
+        let block_number = resolve_block_number(&runtime, &client, block_spec)?;
+
+        Ok(Self::new(runtime, client, block_number))
+    }
+
     /// Retrieves the current block number
     pub fn block_number(&self) -> &U256 {
         &self.block_number
@@ -49,23 +67,170 @@ impl RemoteState {
         })?)
     }
 
-    /// Sets the block number used for calls to the remote Ethereum node.
-    pub fn set_block_number(&mut self, block_number: &U256) {
-        self.block_number = *block_number;
+    /// Sets the block number used for calls to the remote Ethereum node,
+    /// resolving a tag such as `safe` or `finalized` to a concrete number if
+    /// necessary.
+    pub fn set_block_number(&mut self, block_spec: BlockSpec) -> Result<(), StateError> {
+        self.block_number = resolve_block_number(&self.runtime, &self.client, block_spec)?;
+        Ok(())
     }
 
     /// Retrieve the state root of the given block
-    pub fn state_root(&self, block_number: U256) -> Result<B256, StateError> {
+    pub fn state_root(&self, block_spec: BlockSpec) -> Result<B256, StateError> {
         Ok(tokio::task::block_in_place(move || {
+            self.runtime.block_on(self.client.get_block_by_number(block_spec))
+        })?
+        .state_root)
+    }
+
+    /// Derives the base fee per gas that a block mined on top of the forked
+    /// chain would use, by requesting a single block of `eth_feeHistory` for
+    /// the forked block. Used to seed `initial_base_fee_per_gas` when forking
+    /// without an explicit override, so EIP-1559 gas estimation matches the
+    /// forked chain out of the box. Returns `None`, rather than an error, if
+    /// the remote node doesn't support `eth_feeHistory` at all (e.g. a
+    /// pre-EIP-1559 chain) - this is a best-effort hint, not something that
+    /// should fail fork construction.
+    pub fn next_block_base_fee_per_gas(&self) -> Option<U256> {
+        let fee_history = tokio::task::block_in_place(move || {
+            self.runtime.block_on(self.client.fee_history(
+                1,
+                BlockSpec::Number(self.block_number),
+                &[],
+            ))
+        })
+        .ok()?;
+
+        fee_history.base_fee_per_gas.last().copied()
+    }
+
+    /// Resolves a batch of pending account and storage-slot accesses in a
+    /// single JSON-RPC round-trip, rather than one request per access. The
+    /// caller (typically [`CachedRemoteState`]) is expected to populate its
+    /// cache with the result before resuming execution, so that the
+    /// individual `basic`/`storage` calls triggered by the EVM hit the cache
+    /// instead of blocking on the network again.
+    pub fn prefetch(&self, pending: &[PendingAccess]) -> Result<PrefetchedState, StateError> {
+        let requests: Vec<_> = pending
+            .iter()
+            .map(|access| (access.address, access.slots.clone()))
+            .collect();
+
+        let response = tokio::task::block_in_place(move || {
             self.runtime.block_on(
                 self.client
-                    .get_block_by_number(BlockSpec::Number(block_number)),
+                    .get_account_and_storage_batch(&requests, Some(BlockSpec::Number(self.block_number))),
             )
+        })
+        .map_err(StateError::Remote)?;
+
+        let mut accounts = HashMap::with_capacity(pending.len());
+        let mut storage = HashMap::new();
+        for (address, account, slots) in response {
+            accounts.insert(address, account);
+            for (slot, value) in slots {
+                storage.insert((address, slot), value);
+            }
+        }
+
+        Ok(PrefetchedState { accounts, storage })
+    }
+}
+
+/// A single account's worth of pending storage-slot accesses to resolve
+/// together via [`RemoteState::prefetch`].
+#[derive(Clone, Debug)]
+pub struct PendingAccess {
+    /// The account whose info and/or storage slots should be fetched.
+    pub address: Address,
+    /// The storage slots to fetch alongside the account info.
+    pub slots: Vec<U256>,
+}
+
+/// The result of resolving a batch of [`PendingAccess`]es via
+/// [`RemoteState::prefetch`].
+#[derive(Clone, Debug, Default)]
+pub struct PrefetchedState {
+    /// The fetched account info, keyed by address.
+    pub accounts: HashMap<Address, AccountInfo>,
+    /// The fetched storage values, keyed by (address, slot).
+    pub storage: HashMap<(Address, U256), U256>,
+}
+
+/// Resolves a [`BlockSpec`] to a concrete block number, making a single
+/// `eth_getBlockByNumber` request if it names a tag.
+fn resolve_block_number(
+    runtime: &runtime::Handle,
+    client: &RpcClient,
+    block_spec: BlockSpec,
+) -> Result<U256, StateError> {
+    match block_spec {
+        BlockSpec::Number(block_number) => Ok(block_number),
+        BlockSpec::Tag(_) => Ok(tokio::task::block_in_place(|| {
+            runtime.block_on(client.get_block_by_number(block_spec))
         })?
-        .state_root)
+        .number),
     }
 }
 
+/// The result of constructing fork state via [`new_fork_state`].
+#[derive(Debug)]
+pub struct ForkState {
+    /// The `StateRef` implementation fork setup should read state through.
+    pub state: Box<dyn StateRef<Error = StateError> + Send + Sync>,
+    /// The base fee per gas a block mined on top of the forked chain would
+    /// use, derived via `eth_feeHistory`. Intended to seed
+    /// `initial_base_fee_per_gas` when the caller hasn't overridden it.
+    /// `None` if the remote node didn't return fee history (e.g. a
+    /// pre-EIP-1559 chain).
+    pub initial_base_fee_per_gas: Option<U256>,
+    /// A handle to the same cache backing `state`, for batching pending
+    /// account/storage accesses via [`CachedRemoteState::prefetch`] before
+    /// resuming execution. `None` when `verify_proofs` was set, since
+    /// prefetching isn't supported for proof-verified state yet.
+    pub prefetch: Option<Arc<CachedRemoteState<RemoteState>>>,
+}
+
+/// Constructs the `StateRef` implementation fork setup should use for a
+/// forked block: a [`CachedRemoteState`]-wrapped [`RemoteState`] by default,
+/// or a [`VerifiedRemoteState`] when `verify_proofs` opts into validating
+/// every account/storage read against an `eth_getProof` Merkle-Patricia
+/// proof before trusting it. Also derives the forked chain's next base fee
+/// per gas, so callers can seed it without a separate round-trip.
+pub fn new_fork_state(
+    runtime: runtime::Handle,
+    client: Arc<RpcClient>,
+    block_spec: BlockSpec,
+    verify_proofs: bool,
+) -> Result<ForkState, StateError> {
+    let remote = RemoteState::new_at_block_spec(runtime.clone(), client.clone(), block_spec)?;
+    let block_number = *remote.block_number();
+    let initial_base_fee_per_gas = remote.next_block_base_fee_per_gas();
+
+    let (state, prefetch): (
+        Box<dyn StateRef<Error = StateError> + Send + Sync>,
+        Option<Arc<CachedRemoteState<RemoteState>>>,
+    ) = if verify_proofs {
+        let state_root = remote.state_root(BlockSpec::Number(block_number))?;
+        let verified = CachedRemoteState::new(VerifiedRemoteState::new(
+            runtime,
+            client,
+            block_number,
+            state_root,
+        ));
+        (Box::new(verified), None)
+    } else {
+        let cached = Arc::new(CachedRemoteState::new(remote));
+        (Box::new(Arc::clone(&cached)), Some(cached))
+    };
+
+    Ok(ForkState {
+        state,
+        initial_base_fee_per_gas,
+        prefetch,
+    })
+}
+
 impl StateRef for RemoteState {
     type Error = StateError;
 
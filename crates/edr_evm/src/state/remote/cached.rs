@@ -0,0 +1,115 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use revm::{
+    db::StateRef,
+    primitives::{AccountInfo, Bytecode},
+};
+
+use edr_eth::{Address, B256, U256};
+
+use super::super::StateError;
+use super::{PendingAccess, PrefetchedState, RemoteState};
+
+/// A [`StateRef`] wrapper that caches resolved account info and storage
+/// values, so that repeated accesses to the same address/slot within an
+/// execution only pay a JSON-RPC round-trip once. Works over any
+/// `StateRef<Error = StateError>` implementation, so it can wrap either a
+/// plain [`RemoteState`] or a [`super::VerifiedRemoteState`].
+#[derive(Debug)]
+pub struct CachedRemoteState<S> {
+    remote: S,
+    account_cache: Mutex<HashMap<Address, Option<AccountInfo>>>,
+    storage_cache: Mutex<HashMap<(Address, U256), U256>>,
+}
+
+impl<S> CachedRemoteState<S> {
+    /// Constructs a new instance wrapping `remote`, with an empty cache.
+    pub fn new(remote: S) -> Self {
+        Self {
+            remote,
+            account_cache: Mutex::new(HashMap::new()),
+            storage_cache: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl CachedRemoteState<RemoteState> {
+    /// Resolves a batch of pending account/storage accesses via
+    /// [`RemoteState::prefetch`] in a single JSON-RPC round-trip, and
+    /// populates the cache with the result, so that the individual
+    /// `basic`/`storage` calls the EVM subsequently makes for them are served
+    /// from the cache instead of blocking on the network again.
+    pub fn prefetch(&self, pending: &[PendingAccess]) -> Result<(), StateError> {
+        let PrefetchedState { accounts, storage } = self.remote.prefetch(pending)?;
+
+        let mut account_cache = self.account_cache.lock().unwrap();
+        for access in pending {
+            let account = accounts.get(&access.address).cloned();
+            account_cache.insert(access.address, account);
+        }
+        drop(account_cache);
+
+        let mut storage_cache = self.storage_cache.lock().unwrap();
+        storage_cache.extend(storage);
+
+        Ok(())
+    }
+}
+
+impl<S: StateRef<Error = StateError>> StateRef for CachedRemoteState<S> {
+    type Error = StateError;
+
+    fn basic(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        if let Some(account) = self.account_cache.lock().unwrap().get(&address) {
+            return Ok(account.clone());
+        }
+
+        let account = self.remote.basic(address)?;
+        self.account_cache
+            .lock()
+            .unwrap()
+            .insert(address, account.clone());
+
+        Ok(account)
+    }
+
+    fn code_by_hash(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.remote.code_by_hash(code_hash)
+    }
+
+    fn storage(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        if let Some(value) = self.storage_cache.lock().unwrap().get(&(address, index)) {
+            return Ok(*value);
+        }
+
+        let value = self.remote.storage(address, index)?;
+        self.storage_cache
+            .lock()
+            .unwrap()
+            .insert((address, index), value);
+
+        Ok(value)
+    }
+}
+
+// Lets an `Arc<CachedRemoteState<S>>` be used directly as a `StateRef`, so a
+// caller can keep a handle to the same cache (e.g. to call `prefetch`)
+// alongside a type-erased `Box<dyn StateRef>` built from a clone of it.
+impl<S: StateRef<Error = StateError>> StateRef for Arc<CachedRemoteState<S>> {
+    type Error = StateError;
+
+    fn basic(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        (**self).basic(address)
+    }
+
+    fn code_by_hash(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        (**self).code_by_hash(code_hash)
+    }
+
+    fn storage(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        (**self).storage(address, index)
+    }
+}
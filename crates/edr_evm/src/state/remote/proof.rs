@@ -0,0 +1,545 @@
+use alloy_rlp::Header;
+use revm::primitives::{keccak256, AccountInfo, B256, U256};
+
+use edr_eth::remote::eth::{ProofResponse, StorageProof};
+
+use super::super::StateError;
+
+/// The Keccak-256 hash of the RLP encoding of an empty trie node, i.e. the
+/// storage root of an account with no storage.
+const EMPTY_ROOT: B256 = B256::new([
+    0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e,
+    0x5b, 0x48, 0xe0, 0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+]);
+
+/// Verifies an `eth_getProof` account proof against a known-good `state_root`
+/// and returns the verified account, or `None` if the proof establishes that
+/// the account does not exist.
+pub(super) fn verify_account_proof(
+    state_root: B256,
+    proof: &ProofResponse,
+) -> Result<Option<AccountInfo>, StateError> {
+    let key = nibbles(keccak256(proof.address).as_slice());
+
+    match walk_trie(state_root, &key, &proof.account_proof)? {
+        None => {
+            if proof.nonce == 0
+                && proof.balance.is_zero()
+                && proof.storage_hash == EMPTY_ROOT
+                && proof.code_hash == revm::primitives::KECCAK_EMPTY
+            {
+                Ok(None)
+            } else {
+                Err(StateError::InvalidProof)
+            }
+        }
+        Some(leaf) => {
+            let account = decode_account(&leaf)?;
+            if account.nonce != proof.nonce
+                || account.balance != proof.balance
+                || account.storage_root != proof.storage_hash
+                || account.code_hash != proof.code_hash
+            {
+                return Err(StateError::InvalidProof);
+            }
+
+            Ok(Some(AccountInfo {
+                nonce: account.nonce,
+                balance: account.balance,
+                code_hash: account.code_hash,
+                code: None,
+            }))
+        }
+    }
+}
+
+/// Verifies a single `eth_getProof` storage proof against the account's
+/// `storage_hash`.
+pub(super) fn verify_storage_proof(
+    storage_root: B256,
+    storage_proof: &StorageProof,
+) -> Result<U256, StateError> {
+    let key = nibbles(keccak256(storage_proof.key.to_be_bytes::<32>()).as_slice());
+
+    match walk_trie(storage_root, &key, &storage_proof.proof)? {
+        None => {
+            if storage_proof.value.is_zero() {
+                Ok(U256::ZERO)
+            } else {
+                Err(StateError::InvalidProof)
+            }
+        }
+        Some(leaf) => {
+            // A storage slot's value is itself RLP-encoded before being
+            // stored as the leaf's value field, so the value `walk_trie`
+            // returns (one string layer already stripped) still needs a
+            // second decode to reach the raw integer bytes.
+            let value = U256::from_be_slice(rlp_data(&leaf)?);
+
+            if value != storage_proof.value {
+                return Err(StateError::InvalidProof);
+            }
+
+            Ok(value)
+        }
+    }
+}
+
+struct Account {
+    nonce: u64,
+    balance: U256,
+    storage_root: B256,
+    code_hash: B256,
+}
+
+/// Decodes the RLP-encoded `[nonce, balance, storageHash, codeHash]` leaf
+/// value of an account trie node.
+fn decode_account(encoded: &[u8]) -> Result<Account, StateError> {
+    let fields = rlp_list_items(encoded)?;
+    if fields.len() != 4 {
+        return Err(StateError::InvalidProof);
+    }
+
+    Ok(Account {
+        nonce: decode_u64(rlp_data(fields[0])?)?,
+        balance: U256::from_be_slice(rlp_data(fields[1])?),
+        storage_root: decode_b256(rlp_data(fields[2])?)?,
+        code_hash: decode_b256(rlp_data(fields[3])?)?,
+    })
+}
+
+/// Walks a Merkle-Patricia trie `proof` rooted at `root`, following `key`
+/// (already expanded into nibbles), and returns the value stored at the
+/// terminal leaf (with one layer of RLP string-encoding already stripped), or
+/// `None` if the proof demonstrates the key is absent from the trie.
+///
+/// `proof` holds only the nodes large enough to be referenced by hash (32
+/// bytes or more); a child node whose RLP encoding is shorter than that is
+/// embedded directly inline in its parent instead, per the Merkle-Patricia
+/// trie spec, and is resolved from there without a separate array entry.
+fn walk_trie(
+    root: B256,
+    key: &[u8],
+    proof: &[revm::primitives::Bytes],
+) -> Result<Option<Vec<u8>>, StateError> {
+    let Some(first) = proof.first() else {
+        // No nodes at all is only a valid proof for the canonical empty
+        // trie, in which case every key is absent by construction.
+        return if root == EMPTY_ROOT {
+            Ok(None)
+        } else {
+            Err(StateError::InvalidProof)
+        };
+    };
+
+    if keccak256(first) != root {
+        return Err(StateError::InvalidProof);
+    }
+
+    walk_node(first, key, proof, 0)
+}
+
+/// Walks a single trie node (either the hashed `proof[node_index]` or a node
+/// embedded inline in its parent), following `remaining` key nibbles.
+fn walk_node(
+    node: &[u8],
+    remaining: &[u8],
+    proof: &[revm::primitives::Bytes],
+    node_index: usize,
+) -> Result<Option<Vec<u8>>, StateError> {
+    let items = rlp_list_items(node)?;
+
+    match items.len() {
+        17 => {
+            if remaining.is_empty() {
+                let value = rlp_data(items[16])?;
+                return Ok(if value.is_empty() {
+                    None
+                } else {
+                    Some(value.to_vec())
+                });
+            }
+
+            resolve_child(
+                items[remaining[0] as usize],
+                &remaining[1..],
+                proof,
+                node_index,
+            )
+        }
+        2 => {
+            let encoded_path = rlp_data(items[0])?;
+            let (path, is_leaf) = decode_hex_prefix(encoded_path);
+
+            if remaining.len() < path.len() || remaining[..path.len()] != path[..] {
+                // The proof diverges from the requested key, which is only
+                // valid as an exclusion proof on the last supplied node.
+                return if node_index == proof.len() - 1 {
+                    Ok(None)
+                } else {
+                    Err(StateError::InvalidProof)
+                };
+            }
+            let remaining = &remaining[path.len()..];
+
+            if is_leaf {
+                let value = rlp_data(items[1])?;
+                return Ok(Some(value.to_vec()));
+            }
+
+            resolve_child(items[1], remaining, proof, node_index)
+        }
+        _ => Err(StateError::InvalidProof),
+    }
+}
+
+/// Resolves a branch/extension child reference, which is either a 32-byte
+/// hash naming the next entry in `proof`, an empty string (no child), or -
+/// when the child's own RLP encoding is shorter than 32 bytes - the child
+/// node embedded inline as a nested list, already authenticated by its
+/// (already-verified) parent.
+fn resolve_child(
+    item: &[u8],
+    remaining: &[u8],
+    proof: &[revm::primitives::Bytes],
+    node_index: usize,
+) -> Result<Option<Vec<u8>>, StateError> {
+    let mut buf = item;
+    let header = Header::decode(&mut buf).map_err(|_decoder_error| StateError::InvalidProof)?;
+
+    if header.list {
+        return walk_node(item, remaining, proof, node_index);
+    }
+
+    let data = rlp_data(item)?;
+    if data.is_empty() {
+        return Ok(None);
+    }
+
+    let hash = decode_b256(data)?;
+    let next_index = node_index + 1;
+    let next = proof.get(next_index).ok_or(StateError::InvalidProof)?;
+    if keccak256(next) != hash {
+        return Err(StateError::InvalidProof);
+    }
+
+    walk_node(next, remaining, proof, next_index)
+}
+
+/// Splits the payload of an RLP-encoded list into its top-level items, each
+/// still carrying its own RLP header.
+fn rlp_list_items(node: &[u8]) -> Result<Vec<&[u8]>, StateError> {
+    let mut buf = node;
+    let header = Header::decode(&mut buf).map_err(|_decoder_error| StateError::InvalidProof)?;
+    if !header.list || header.payload_length > buf.len() {
+        return Err(StateError::InvalidProof);
+    }
+
+    let mut payload = &buf[..header.payload_length];
+    let mut items = Vec::new();
+    while !payload.is_empty() {
+        let remaining_before = payload;
+        let item_header =
+            Header::decode(&mut payload).map_err(|_decoder_error| StateError::InvalidProof)?;
+        if item_header.payload_length > payload.len() {
+            return Err(StateError::InvalidProof);
+        }
+
+        let item_len = remaining_before.len() - payload.len() + item_header.payload_length;
+        items.push(&remaining_before[..item_len]);
+        payload = &payload[item_header.payload_length..];
+    }
+
+    Ok(items)
+}
+
+/// Decodes a single RLP string item, returning its payload bytes.
+fn rlp_data(item: &[u8]) -> Result<&[u8], StateError> {
+    let mut buf = item;
+    let header = Header::decode(&mut buf).map_err(|_decoder_error| StateError::InvalidProof)?;
+    if header.list || header.payload_length > buf.len() {
+        return Err(StateError::InvalidProof);
+    }
+
+    Ok(&buf[..header.payload_length])
+}
+
+fn decode_u64(data: &[u8]) -> Result<u64, StateError> {
+    if data.len() > 8 || data.first() == Some(&0) {
+        // A canonical RLP integer is never longer than necessary and never
+        // carries a leading zero byte (zero itself is the empty string).
+        return Err(StateError::InvalidProof);
+    }
+
+    let mut padded = [0u8; 8];
+    padded[8 - data.len()..].copy_from_slice(data);
+    Ok(u64::from_be_bytes(padded))
+}
+
+fn decode_b256(data: &[u8]) -> Result<B256, StateError> {
+    if data.len() != 32 {
+        return Err(StateError::InvalidProof);
+    }
+    Ok(B256::from_slice(data))
+}
+
+/// Decodes a compact hex-prefix encoded partial path (Ethereum Yellow Paper,
+/// appendix C), returning its nibbles and whether it terminates in a leaf.
+fn decode_hex_prefix(encoded: &[u8]) -> (Vec<u8>, bool) {
+    let Some(&first) = encoded.first() else {
+        return (Vec::new(), false);
+    };
+
+    let is_leaf = first & 0x20 != 0;
+    let is_odd = first & 0x10 != 0;
+
+    let mut path = Vec::with_capacity(encoded.len() * 2);
+    if is_odd {
+        path.push(first & 0x0f);
+    }
+    for byte in &encoded[1..] {
+        path.push(byte >> 4);
+        path.push(byte & 0x0f);
+    }
+
+    (path, is_leaf)
+}
+
+/// Expands a byte slice into its individual nibbles, most-significant first.
+fn nibbles(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        out.push(byte >> 4);
+        out.push(byte & 0x0f);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_rlp::{BufMut, Encodable};
+    use revm::primitives::Bytes;
+
+    use super::*;
+
+    #[test]
+    fn nibbles_expands_each_byte() {
+        assert_eq!(nibbles(&[0xab, 0x0f]), vec![0xa, 0xb, 0x0, 0xf]);
+        assert_eq!(nibbles(&[]), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn decode_hex_prefix_even_leaf() {
+        // Prefix 0x20 marks an even-length leaf path.
+        let (path, is_leaf) = decode_hex_prefix(&[0x20, 0xab, 0xcd]);
+        assert!(is_leaf);
+        assert_eq!(path, vec![0xa, 0xb, 0xc, 0xd]);
+    }
+
+    #[test]
+    fn decode_hex_prefix_odd_extension() {
+        // Prefix nibble 0x1 marks an odd-length extension path, whose first
+        // nibble (0x2) is packed into the prefix byte itself.
+        let (path, is_leaf) = decode_hex_prefix(&[0x12, 0xab]);
+        assert!(!is_leaf);
+        assert_eq!(path, vec![0x2, 0xa, 0xb]);
+    }
+
+    #[test]
+    fn decode_hex_prefix_empty_is_not_a_leaf() {
+        let (path, is_leaf) = decode_hex_prefix(&[]);
+        assert!(!is_leaf);
+        assert!(path.is_empty());
+    }
+
+    #[test]
+    fn decode_u64_rejects_oversized_field() {
+        // A canonical RLP-encoded u64 never exceeds 8 bytes; a longer field
+        // must be rejected rather than silently truncated.
+        assert!(matches!(
+            decode_u64(&[0u8; 9]),
+            Err(StateError::InvalidProof)
+        ));
+        assert_eq!(decode_u64(&[0x01, 0x02]).unwrap(), 0x0102);
+    }
+
+    #[test]
+    fn decode_u64_rejects_non_canonical_leading_zero() {
+        assert!(matches!(
+            decode_u64(&[0x00, 0x01]),
+            Err(StateError::InvalidProof)
+        ));
+        assert!(matches!(decode_u64(&[0x00]), Err(StateError::InvalidProof)));
+    }
+
+    fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+        let payload_length: usize = items.iter().map(Vec::len).sum();
+        let header = Header {
+            list: true,
+            payload_length,
+        };
+
+        let mut out = Vec::new();
+        header.encode(&mut out);
+        for item in items {
+            out.put_slice(item);
+        }
+        out
+    }
+
+    fn encode_string(data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        data.encode(&mut out);
+        out
+    }
+
+    /// The inverse of `decode_hex_prefix`, for building test fixtures.
+    fn encode_hex_prefix(path: &[u8], is_leaf: bool) -> Vec<u8> {
+        let mut flag = if is_leaf { 0x20 } else { 0x00 };
+        let mut nibbles = path.to_vec();
+        if nibbles.len() % 2 == 1 {
+            flag |= 0x10 | nibbles.remove(0);
+        }
+
+        let mut out = vec![flag];
+        for chunk in nibbles.chunks(2) {
+            out.push((chunk[0] << 4) | chunk[1]);
+        }
+        out
+    }
+
+    #[test]
+    fn walk_trie_resolves_single_leaf_root() {
+        // A trie with a single leaf at the root: the key's full nibble path
+        // is stored in the (even-length) leaf prefix, with an arbitrary
+        // value.
+        let value = encode_string(b"hello");
+        let leaf = encode_list(&[encode_string(&[0x20, 0xab]), value.clone()]);
+        let root = keccak256(&leaf);
+
+        let proof = vec![Bytes::from(leaf)];
+        let key = nibbles(&[0xab]);
+
+        let resolved = walk_trie(root, &key, &proof).expect("proof verifies");
+        assert_eq!(resolved, Some(rlp_data(&value).unwrap().to_vec()));
+    }
+
+    #[test]
+    fn walk_trie_rejects_tampered_node() {
+        let value = encode_string(b"hello");
+        let leaf = encode_list(&[encode_string(&[0x20, 0xab]), value]);
+        let root = keccak256(&leaf);
+
+        let mut tampered = leaf.clone();
+        *tampered.last_mut().unwrap() ^= 0xff;
+
+        let proof = vec![Bytes::from(tampered)];
+        let key = nibbles(&[0xab]);
+
+        assert!(matches!(
+            walk_trie(root, &key, &proof),
+            Err(StateError::InvalidProof)
+        ));
+    }
+
+    #[test]
+    fn walk_trie_proves_exclusion_via_divergent_leaf() {
+        // The only leaf in the trie is for a different key, which proves the
+        // requested key is absent as long as it's the final proof node.
+        let value = encode_string(b"hello");
+        let leaf = encode_list(&[encode_string(&[0x20, 0xab]), value]);
+        let root = keccak256(&leaf);
+
+        let proof = vec![Bytes::from(leaf)];
+        let key = nibbles(&[0xcd]);
+
+        assert_eq!(walk_trie(root, &key, &proof).expect("excluded"), None);
+    }
+
+    #[test]
+    fn verify_storage_proof_decodes_double_rlp_encoded_value() {
+        // A storage slot's scalar value is itself RLP-encoded before being
+        // stored as a trie leaf's value field, so the on-the-wire leaf
+        // carries a doubly-encoded value. A value requiring more than one
+        // byte (e.g. 0x0100) catches a decoder that only strips one layer.
+        let slot = U256::from(0x42u64);
+        let value = U256::from(0x0100u64);
+
+        let key = nibbles(keccak256(slot.to_be_bytes::<32>()).as_slice());
+        let encoded_path = encode_string(&encode_hex_prefix(&key, true));
+        let doubly_encoded_value = encode_string(&encode_string(
+            &value.to_be_bytes::<32>()[value.leading_zeros() as usize / 8..],
+        ));
+        let leaf = encode_list(&[encoded_path, doubly_encoded_value]);
+        let root = keccak256(&leaf);
+
+        let storage_proof = StorageProof {
+            key: slot,
+            value,
+            proof: vec![Bytes::from(leaf)],
+        };
+
+        let resolved =
+            verify_storage_proof(root, &storage_proof).expect("proof verifies");
+        assert_eq!(resolved, value);
+    }
+
+    #[test]
+    fn walk_trie_proves_exclusion_via_empty_branch_slot() {
+        let mut items = vec![encode_string(&[]); 17];
+        items[1] = encode_string(&keccak256(b"child").0);
+        let branch = encode_list(&items);
+        let root = keccak256(&branch);
+
+        let proof = vec![Bytes::from(branch)];
+        // Nibble 0x0 has an empty branch slot.
+        let key = vec![0x0, 0x1];
+
+        assert_eq!(walk_trie(root, &key, &proof).expect("excluded"), None);
+    }
+
+    #[test]
+    fn walk_trie_proves_exclusion_against_the_empty_trie() {
+        // An account with no storage (or a brand-new state trie) has
+        // `storageHash`/`stateRoot` equal to `EMPTY_ROOT` and an empty
+        // `proof` array - every key is absent by definition.
+        let key = nibbles(&[0xab]);
+
+        assert_eq!(walk_trie(EMPTY_ROOT, &key, &[]).expect("excluded"), None);
+    }
+
+    #[test]
+    fn walk_trie_rejects_empty_proof_against_a_nonempty_root() {
+        let key = nibbles(&[0xab]);
+        let root = keccak256(b"not the empty trie");
+
+        assert!(matches!(
+            walk_trie(root, &key, &[]),
+            Err(StateError::InvalidProof)
+        ));
+    }
+
+    #[test]
+    fn walk_trie_resolves_embedded_child_node() {
+        // A child whose RLP encoding is shorter than 32 bytes is embedded
+        // directly in its parent's branch slot instead of being referenced
+        // by hash, so it never gets its own entry in the `proof` array.
+        let value = encode_string(b"hi");
+        let embedded_leaf = encode_list(&[encode_string(&[0x20, 0xcd]), value.clone()]);
+        assert!(
+            embedded_leaf.len() < 32,
+            "fixture must actually be embeddable"
+        );
+
+        let mut items = vec![encode_string(&[]); 17];
+        items[0xa] = embedded_leaf;
+        let branch = encode_list(&items);
+        let root = keccak256(&branch);
+
+        let proof = vec![Bytes::from(branch)];
+        let key = vec![0xa, 0xc, 0xd];
+
+        let resolved = walk_trie(root, &key, &proof).expect("proof verifies");
+        assert_eq!(resolved, Some(rlp_data(&value).unwrap().to_vec()));
+    }
+}
@@ -15,10 +15,14 @@ use crate::{account::GenesisAccount, cast::TryCast, config::SpecId};
 pub struct ForkConfig {
     /// The URL of the JSON-RPC endpoint to fork from
     pub json_rpc_url: String,
-    /// The block number to fork from. If not provided, the latest safe block is
-    /// used.
+    /// The block number to fork from. If not provided, defaults to the
+    /// `safe` block tag, which the forking layer resolves to a concrete
+    /// block number once, at fork construction time (see
+    /// `edr_evm::state::new_fork_state`).
     pub block_number: Option<BigInt>,
-    // TODO: add http_headers,
+    /// The HTTP headers to use when forking, e.g. an `Authorization` bearer
+    /// token or a provider-specific API-key header for gated RPC endpoints
+    pub http_headers: Option<HashMap<String, String>>,
 }
 
 /// Configuration for a provider
@@ -39,12 +43,25 @@ pub struct ProviderConfig {
     /// The configuration for forking a blockchain. If not provided, a local
     /// blockchain will be created
     pub fork: Option<ForkConfig>,
+    /// The maximum number of times to retry a failed JSON-RPC request to the
+    /// forked node, using exponential backoff (with jitter) between attempts
+    /// and honoring `Retry-After` on `429` responses. Maps to
+    /// `edr_eth::remote::RetryConfig::max_retries`. Defaults to a built-in
+    /// value if not provided
+    pub fork_request_retries: Option<u8>,
+    /// The maximum total time, in milliseconds, to spend retrying a single
+    /// forked JSON-RPC request before giving up. Maps to
+    /// `edr_eth::remote::RetryConfig::max_elapsed`. Defaults to a built-in
+    /// value if not provided
+    pub fork_request_timeout_ms: Option<BigInt>,
     /// The genesis accounts of the blockchain
     pub genesis_accounts: Vec<GenesisAccount>,
     /// The hardfork of the blockchain
     pub hardfork: SpecId,
-    /// The initial base fee per gas of the blockchain. Required for EIP-1559
-    /// transactions and later
+    /// The initial base fee per gas of the blockchain, required for EIP-1559
+    /// transactions and later. If not provided and `fork` is set, the forking
+    /// layer derives it from the forked chain's next base fee per gas via
+    /// `eth_feeHistory` (see `ForkState::initial_base_fee_per_gas`).
     pub initial_base_fee_per_gas: Option<BigInt>,
     /// The initial date of the blockchain, in seconds since the Unix epoch
     pub initial_date: Option<BigInt>,
@@ -61,7 +78,7 @@ impl TryFrom<ForkConfig> for edr_rpc_hardhat::config::ForkConfig {
         Ok(Self {
             json_rpc_url: value.json_rpc_url,
             block_number,
-            http_headers: None,
+            http_headers: value.http_headers,
         })
     }
 }
@@ -87,6 +104,13 @@ impl TryFrom<ProviderConfig> for edr_provider::ProviderConfig {
             chain_id: value.chain_id.try_cast()?,
             coinbase: Address::from_slice(value.coinbase.as_ref()),
             fork: value.fork.map(TryInto::try_into).transpose()?,
+            fork_request_retries: value.fork_request_retries,
+            fork_request_timeout: value
+                .fork_request_timeout_ms
+                .map(|timeout_ms| {
+                    napi::Result::Ok(Duration::from_millis(timeout_ms.try_cast()?))
+                })
+                .transpose()?,
             genesis_accounts: HashMap::new(),
             hardfork: value.hardfork.try_into()?,
             initial_base_fee_per_gas: value